@@ -3,32 +3,384 @@ use serde::{Deserialize, Serialize};
 use weil_macros::{constructor, mutate, query, secured, smart_contract, WeilType};
 use std::collections::BTreeMap;
 
+// monotonic, timezone-aware timestamp. wire format is the string
+// `<millis_since_epoch>+<tz_offset_minutes>`, so existing string-typed callers keep working
+// while internally we compare instants by `millis_since_epoch` alone (the tz offset is display-only).
+#[derive(Debug, Clone, Copy, WeilType)]
+pub struct Timestamp {
+    pub millis_since_epoch: i64,
+    pub tz_offset_minutes: i32,
+}
+
+impl PartialEq for Timestamp {
+    fn eq(&self, other: &Self) -> bool {
+        self.millis_since_epoch == other.millis_since_epoch
+    }
+}
+
+impl Eq for Timestamp {}
+
+impl PartialOrd for Timestamp {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Timestamp {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.millis_since_epoch.cmp(&other.millis_since_epoch)
+    }
+}
+
+impl Timestamp {
+    fn to_wire_string(self) -> String {
+        format!("{}+{}", self.millis_since_epoch, self.tz_offset_minutes)
+    }
+
+    fn parse_wire_string(value: &str) -> Option<Timestamp> {
+        let (millis_part, offset_part) = value.split_once('+')?;
+        Some(Timestamp {
+          millis_since_epoch: millis_part.parse().ok()?,
+          tz_offset_minutes: offset_part.parse().ok()?,
+        })
+    }
+}
+
+impl Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_wire_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Timestamp::parse_wire_string(&value)
+          .ok_or_else(|| serde::de::Error::custom("invalid timestamp, expected `<millis_since_epoch>+<tz_offset_minutes>`"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone,PartialEq)]
+pub enum KeyScheme {
+    Ed25519,
+    Secp256k1,
+}
+
+impl KeyScheme {
+    fn parse(scheme: &str) -> Option<KeyScheme> {
+        match scheme {
+            "ed25519" => Some(KeyScheme::Ed25519),
+            "secp256k1" => Some(KeyScheme::Secp256k1),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            KeyScheme::Ed25519 => "ed25519",
+            KeyScheme::Secp256k1 => "secp256k1",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
+pub struct UserKey {
+    pub public_key: String,
+    pub key_scheme: KeyScheme,
+}
+
 #[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
 pub struct ProofInfo {
     pub proof_hash: String,
     pub proof_type: String,
-    pub timestamp: String,
+    pub cid: String,
+    pub mime_type: String,
+    pub timestamp: Timestamp,
+    pub public_key: String,
+    pub signature: String,
+    pub key_scheme: KeyScheme,
+}
+
+// checks that `proof_hash` (hex) matches the multihash digest embedded in `cid` (a CIDv1 string),
+// so a proof can't claim a content-addressed location it doesn't actually hash to
+fn cid_matches_hash(cid: &str, proof_hash: &str) -> bool {
+    const SHA2_256_MULTICODEC: u64 = 0x12;
+
+    let Ok(parsed) = cid::Cid::try_from(cid) else { return false };
+    // reject anything but sha2-256: an identity-multihash (code 0x00) CID embeds the raw bytes
+    // the caller chose as its "digest", so without this check any proof_hash could be matched by
+    // minting a CID around it rather than one that's actually the hash of retrievable content
+    if parsed.hash().code() != SHA2_256_MULTICODEC {
+      return false;
+    }
+    hex::encode(parsed.hash().digest()).eq_ignore_ascii_case(proof_hash)
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone,PartialEq)]
+pub enum ComplaintStatus {
+    Filed,
+    UnderInvestigation,
+    Escalated,
+    Resolved,
+    Rejected,
+}
+
+impl ComplaintStatus {
+    fn parse(status: &str) -> Option<ComplaintStatus> {
+        match status {
+            "FILED" => Some(ComplaintStatus::Filed),
+            "UNDER_INVESTIGATION" => Some(ComplaintStatus::UnderInvestigation),
+            "ESCALATED" => Some(ComplaintStatus::Escalated),
+            "RESOLVED" => Some(ComplaintStatus::Resolved),
+            "REJECTED" => Some(ComplaintStatus::Rejected),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ComplaintStatus::Filed => "FILED",
+            ComplaintStatus::UnderInvestigation => "UNDER_INVESTIGATION",
+            ComplaintStatus::Escalated => "ESCALATED",
+            ComplaintStatus::Resolved => "RESOLVED",
+            ComplaintStatus::Rejected => "REJECTED",
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.allowed_next().is_empty()
+    }
+
+    // legal next states from this status; terminal states (Resolved, Rejected) allow none
+    fn allowed_next(&self) -> &'static [ComplaintStatus] {
+        match self {
+            ComplaintStatus::Filed => &[ComplaintStatus::UnderInvestigation, ComplaintStatus::Escalated, ComplaintStatus::Rejected],
+            ComplaintStatus::UnderInvestigation => &[ComplaintStatus::Escalated, ComplaintStatus::Resolved, ComplaintStatus::Rejected],
+            ComplaintStatus::Escalated => &[ComplaintStatus::UnderInvestigation, ComplaintStatus::Resolved, ComplaintStatus::Rejected],
+            ComplaintStatus::Resolved => &[],
+            ComplaintStatus::Rejected => &[],
+        }
+    }
+}
+
+// true when a complaint's status string is one escalate_overdue/get_overdue should consider
+// (i.e. still open and not already escalated); unparseable statuses are never escalatable
+fn is_escalatable(status: &str) -> bool {
+    matches!(ComplaintStatus::parse(status), Some(ComplaintStatus::Filed) | Some(ComplaintStatus::UnderInvestigation))
 }
 
 #[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
 pub struct ComplaintInfo {
     pub user_id: String,
     pub complaint_hash: String,
-    pub timestamp: String,
+    pub timestamp: Timestamp,
     pub status: String,
-    pub last_status_update: String,
+    pub last_status_update: Timestamp,
     pub proofs: Vec<ProofInfo>,
+    pub public_key: String,
+    pub signature: String,
+    pub key_scheme: KeyScheme,
+    pub sla_deadline: Option<Timestamp>,
+}
+
+// verifies that `signature` over `message`, produced with `key_scheme`, matches `public_key`.
+// public_key and signature are hex-encoded on the wire.
+fn verify_signature(key_scheme: &KeyScheme, public_key: &str, signature: &str, message: &[u8]) -> bool {
+    match key_scheme {
+        KeyScheme::Ed25519 => verify_ed25519(public_key, signature, message),
+        KeyScheme::Secp256k1 => verify_secp256k1(public_key, signature, message),
+    }
+}
+
+fn verify_ed25519(public_key: &str, signature: &str, message: &[u8]) -> bool {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    let Ok(pk_bytes) = hex::decode(public_key) else { return false };
+    let Ok(pk_bytes): Result<[u8; 32], _> = pk_bytes.try_into() else { return false };
+    let Ok(verifying_key) = VerifyingKey::from_bytes(&pk_bytes) else { return false };
+
+    let Ok(sig_bytes) = hex::decode(signature) else { return false };
+    let Ok(sig_bytes): Result<[u8; 64], _> = sig_bytes.try_into() else { return false };
+    let signature = Signature::from_bytes(&sig_bytes);
+
+    verifying_key.verify(message, &signature).is_ok()
+}
+
+fn verify_secp256k1(public_key: &str, signature: &str, message: &[u8]) -> bool {
+    use k256::ecdsa::signature::Verifier;
+    use k256::ecdsa::{Signature, VerifyingKey};
+
+    let Ok(pk_bytes) = hex::decode(public_key) else { return false };
+    let Ok(verifying_key) = VerifyingKey::from_sec1_bytes(&pk_bytes) else { return false };
+
+    let Ok(sig_bytes) = hex::decode(signature) else { return false };
+    let Ok(signature) = Signature::from_der(&sig_bytes).or_else(|_| Signature::from_slice(&sig_bytes)) else { return false };
+
+    verifying_key.verify(message, &signature).is_ok()
+}
+
+// appends `field` to `buf` prefixed with its length (u32 LE) so concatenated fields can't be
+// reinterpreted at a different boundary, e.g. complaint_id="C1",complaint_hash="Hx" must not
+// serialize the same as complaint_id="C",complaint_hash="1Hx"
+fn encode_field(buf: &mut Vec<u8>, field: &[u8]) {
+    buf.extend_from_slice(&(field.len() as u32).to_le_bytes());
+    buf.extend_from_slice(field);
+}
+
+// canonical message a caller must sign: a domain tag identifying the call this signature is for,
+// then complaint_id || complaint_hash || user_id || timestamp, each field length-prefixed (see
+// encode_field). The domain tag stops a signature collected for one call from being replayed as
+// though it covers a different call whose encoded fields happen to line up
+const COMPLAINT_REGISTER_DOMAIN: &[u8] = b"complaint_register_v1";
+const REGISTER_PROOF_DOMAIN: &[u8] = b"register_proof_v1";
+
+fn complaint_message(complaint_id: &str, complaint_hash: &str, user_id: &str, timestamp: Timestamp) -> Vec<u8> {
+    let mut message = Vec::new();
+    encode_field(&mut message, COMPLAINT_REGISTER_DOMAIN);
+    encode_field(&mut message, complaint_id.as_bytes());
+    encode_field(&mut message, complaint_hash.as_bytes());
+    encode_field(&mut message, user_id.as_bytes());
+    encode_field(&mut message, timestamp.to_wire_string().as_bytes());
+    message
+}
+
+// canonical message a caller must sign: domain tag, then complaint_id || proof_hash || proof_type
+// || cid || mime_type || timestamp, each field length-prefixed (see encode_field); cid and
+// mime_type must be bound here too, otherwise a relayer could swap either after the signature
+// was collected
+fn proof_message(complaint_id: &str, proof_hash: &str, proof_type: &str, cid: &str, mime_type: &str, timestamp: Timestamp) -> Vec<u8> {
+    let mut message = Vec::new();
+    encode_field(&mut message, REGISTER_PROOF_DOMAIN);
+    encode_field(&mut message, complaint_id.as_bytes());
+    encode_field(&mut message, proof_hash.as_bytes());
+    encode_field(&mut message, proof_type.as_bytes());
+    encode_field(&mut message, cid.as_bytes());
+    encode_field(&mut message, mime_type.as_bytes());
+    encode_field(&mut message, timestamp.to_wire_string().as_bytes());
+    message
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
+pub struct MerkleSibling {
+    pub hash: String,
+    // true when this sibling sits to the left of the node on the path (i.e. the path node is the right child)
+    pub is_left: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
+pub struct ComplaintProof {
+    pub leaf_hash: String,
+    pub siblings: Vec<MerkleSibling>,
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(Sha256::digest(data))
+}
+
+// sub-root over a complaint's proofs, used as part of the complaint's leaf hash; folds in every
+// field of ProofInfo so the root actually attests to a proof's cid/mime_type/signer, not just
+// the subset that existed when this function was first written
+fn merkle_of_proofs(proofs: &[ProofInfo]) -> String {
+    let leaves: Vec<String> = proofs
+        .iter()
+        .map(|p| sha256_hex(format!(
+            "{}{}{}{}{}{}{}{}",
+            p.proof_hash, p.proof_type, p.cid, p.mime_type, p.timestamp.to_wire_string(), p.public_key, p.signature, p.key_scheme.as_str(),
+        ).as_bytes()))
+        .collect();
+    build_merkle_levels(leaves)
+        .last()
+        .and_then(|level| level.first().cloned())
+        .unwrap_or_else(|| sha256_hex(b""))
+}
+
+// folds in every field of ComplaintInfo (besides the proofs, already covered by proofs_root) so
+// the leaf attests to a complaint's signer and SLA deadline, not just its hash/status/timestamp
+fn complaint_leaf_hash(complaint_id: &str, complaint: &ComplaintInfo) -> String {
+    let proofs_root = merkle_of_proofs(&complaint.proofs);
+    let sla_deadline = complaint.sla_deadline.map(Timestamp::to_wire_string).unwrap_or_default();
+    sha256_hex(format!(
+        "{complaint_id}{}{}{}{}{}{}{}{}{}{proofs_root}",
+        complaint.user_id, complaint.complaint_hash, complaint.timestamp.to_wire_string(),
+        complaint.status, complaint.last_status_update.to_wire_string(),
+        complaint.public_key, complaint.signature, complaint.key_scheme.as_str(), sla_deadline,
+    ).as_bytes())
+}
+
+// builds every level of a binary merkle tree from its leaves, root last; duplicates the last
+// node of a level when it has an odd count
+fn build_merkle_levels(leaves: Vec<String>) -> Vec<Vec<String>> {
+    if leaves.is_empty() {
+        return vec![vec![sha256_hex(b"")]];
+    }
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let prev = levels.last().unwrap();
+        let mut next = Vec::with_capacity(prev.len().div_ceil(2));
+        let mut i = 0;
+        while i < prev.len() {
+            let left = &prev[i];
+            let right = if i + 1 < prev.len() { &prev[i + 1] } else { left };
+            next.push(sha256_hex(format!("{left}{right}").as_bytes()));
+            i += 2;
+        }
+        levels.push(next);
+    }
+    levels
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone,PartialEq)]
+pub enum EventCategory {
+    Create,
+    Modify,
+    Access,
+    Remove,
+}
+
+#[derive(Debug, Serialize, Deserialize,WeilType,Clone)]
+pub struct AuditEvent {
+    pub seq: u64,
+    pub action_id: String,
+    pub category: EventCategory,
+    // for Complaint.Register and Proof.Submit this is the complaint's user_id, derived from the
+    // Ed25519/secp256k1 signer verified for that mutate (see complaint_register/register_proof),
+    // so it cannot be spoofed by the caller. UserKey.Register, Complaint.UpdateStatus and
+    // Complaint.Escalate verify no per-call signature, so `actor` there remains a caller-supplied,
+    // caller-attested string; the hash chain still detects tampering with past entries either way
+    pub actor: String,
+    pub target_complaint_id: String,
+    pub timestamp: String,
+    pub prev_hash: String,
+    pub event_hash: String,
+}
+
+fn genesis_hash() -> String {
+    sha256_hex(b"genesis")
+}
+
+fn event_hash(prev_hash: &str, seq: u64, action_id: &str, actor: &str, target_complaint_id: &str, timestamp: &str) -> String {
+    sha256_hex(format!("{prev_hash}{seq}{action_id}{actor}{target_complaint_id}{timestamp}").as_bytes())
 }
 
 trait AuditLayer {
     fn new() -> Result<Self, String>
     where
         Self: Sized;
-    async fn complaint_register(&mut self, complaint_id: String, complaint_hash: String, user_id: String, timestamp: String) -> bool;
-    async fn register_proof(&mut self, complaint_id: String, proof_hash: String, proof_type: String, timestamp: String) -> bool;
-    async fn update_complaint_status(&mut self, complaint_id: String, status: String, timestamp: String) -> bool;
+    async fn register_user_key(&mut self, user_id: String, public_key: String, key_scheme: String, actor: String) -> bool;
+    async fn complaint_register(&mut self, complaint_id: String, complaint_hash: String, user_id: String, timestamp: Timestamp, public_key: String, signature: String, key_scheme: String, sla_deadline: Option<Timestamp>) -> bool;
+    async fn register_proof(&mut self, complaint_id: String, proof_hash: String, proof_type: String, cid: String, mime_type: String, timestamp: Timestamp, public_key: String, signature: String, key_scheme: String) -> bool;
+    async fn update_complaint_status(&mut self, complaint_id: String, status: String, timestamp: Timestamp, actor: String) -> bool;
+    async fn allowed_transitions(&self, complaint_id: String) -> Vec<String>;
+    async fn escalate_overdue(&mut self, now: Timestamp, actor: String) -> Vec<String>;
+    async fn get_overdue(&self, now: Timestamp) -> Vec<String>;
     async fn get_complaints(&self) -> std::collections::BTreeMap<String, ComplaintInfo>;
     async fn get_complaint(&self, complaint_id: String) -> ComplaintInfo;
+    async fn get_complaint_proof(&self, complaint_id: String) -> ComplaintProof;
+    async fn get_proofs_by_type(&self, complaint_id: String, mime_type: String) -> Vec<ProofInfo>;
+    async fn complaints_root(&self) -> String;
+    async fn get_audit_log(&self) -> Vec<AuditEvent>;
+    async fn verify_audit_chain(&self) -> bool;
     fn tools(&self) -> String;
     fn prompts(&self) -> String;
 }
@@ -37,6 +389,67 @@ trait AuditLayer {
 pub struct AuditLayerContractState {
     // define your contract state here!
     complaints: BTreeMap<String, ComplaintInfo>,
+    user_keys: BTreeMap<String, UserKey>,
+    // cached merkle tree over `complaints` (sorted by key), leaves first, root last
+    merkle_levels: Vec<Vec<String>>,
+    // append-only, hash-chained log of every mutation applied to this contract
+    audit_log: Vec<AuditEvent>,
+}
+
+impl AuditLayerContractState {
+    // records one entry in the hash chain; call only after a mutation has actually taken effect
+    fn append_audit_event(&mut self, action_id: &str, category: EventCategory, actor: &str, target_complaint_id: &str, timestamp: &str) {
+        let seq = self.audit_log.len() as u64;
+        let prev_hash = self.audit_log.last().map(|e| e.event_hash.clone()).unwrap_or_else(genesis_hash);
+        let event_hash = event_hash(&prev_hash, seq, action_id, actor, target_complaint_id, timestamp);
+        self.audit_log.push(AuditEvent {
+          seq,
+          action_id: action_id.to_string(),
+          category,
+          actor: actor.to_string(),
+          target_complaint_id: target_complaint_id.to_string(),
+          timestamp: timestamp.to_string(),
+          prev_hash,
+          event_hash,
+        });
+    }
+
+    // full rebuild: needed whenever the set of complaint ids changes, since every leaf's index shifts
+    fn rebuild_merkle(&mut self) {
+        let leaves: Vec<String> = self
+          .complaints
+          .iter()
+          .map(|(id, complaint)| complaint_leaf_hash(id, complaint))
+          .collect();
+        self.merkle_levels = build_merkle_levels(leaves);
+    }
+
+    // a complaint's content changed but the key set didn't: only the leaf-to-root path moves
+    fn update_merkle_path(&mut self, complaint_id: &str) {
+        let Some(index) = self.complaints.keys().position(|id| id == complaint_id) else {
+          return;
+        };
+        let Some(complaint) = self.complaints.get(complaint_id) else {
+          return;
+        };
+        if self.merkle_levels.is_empty() || self.merkle_levels[0].len() != self.complaints.len() {
+          self.rebuild_merkle();
+          return;
+        }
+        self.merkle_levels[0][index] = complaint_leaf_hash(complaint_id, complaint);
+        let mut idx = index;
+        for level in 0..self.merkle_levels.len() - 1 {
+          let level_len = self.merkle_levels[level].len();
+          let (left, right) = if idx % 2 == 0 {
+            let sibling = if idx + 1 < level_len { idx + 1 } else { idx };
+            (self.merkle_levels[level][idx].clone(), self.merkle_levels[level][sibling].clone())
+          } else {
+            (self.merkle_levels[level][idx - 1].clone(), self.merkle_levels[level][idx].clone())
+          };
+          idx /= 2;
+          self.merkle_levels[level + 1][idx] = sha256_hex(format!("{left}{right}").as_bytes());
+        }
+    }
 }
 
 #[smart_contract]
@@ -48,60 +461,191 @@ impl AuditLayer for AuditLayerContractState {
     {
         Ok(Self {
           complaints: BTreeMap::new(),
+          user_keys: BTreeMap::new(),
+          merkle_levels: Vec::new(),
+          audit_log: Vec::new(),
         })
     }
 
+    #[mutate]
+    async fn register_user_key(&mut self, user_id: String, public_key: String, key_scheme: String, actor: String) -> bool {
+        if self.user_keys.contains_key(&user_id) {
+          // first registration wins: once a user_id is bound to a key, the signature checks
+          // downstream (complaint_register, register_proof) are only as trustworthy as this
+          // binding being unforgeable, so it cannot be silently overwritten by a later caller
+          return false;
+        }
+        let Some(key_scheme) = KeyScheme::parse(&key_scheme) else {
+          return false;
+        };
+        self.user_keys.insert(user_id, UserKey { public_key, key_scheme });
+        self.append_audit_event("UserKey.Register", EventCategory::Create, &actor, "", "");
+        true
+    }
 
     #[mutate]
-    async fn complaint_register(&mut self, complaint_id: String, complaint_hash: String, user_id: String, timestamp: String) -> bool {
+    async fn complaint_register(&mut self, complaint_id: String, complaint_hash: String, user_id: String, timestamp: Timestamp, public_key: String, signature: String, key_scheme: String, sla_deadline: Option<Timestamp>) -> bool {
         if self.complaints.contains_key(&complaint_id) {
           return false;
         }
+        let Some(key_scheme) = KeyScheme::parse(&key_scheme) else {
+          return false;
+        };
+        let Some(registered_key) = self.user_keys.get(&user_id) else {
+          return false;
+        };
+        if registered_key.public_key != public_key || registered_key.key_scheme != key_scheme {
+          return false;
+        }
+        let message = complaint_message(&complaint_id, &complaint_hash, &user_id, timestamp);
+        if !verify_signature(&key_scheme, &public_key, &signature, &message) {
+          return false;
+        }
+        // derived from the verified signer, not caller-supplied: user_id is only reachable here
+        // by whoever holds the key registered for it, so this attribution can't be spoofed
+        let actor = user_id.clone();
         let new_complaint = ComplaintInfo {
           user_id,
           complaint_hash,
-          timestamp: timestamp.clone(),
+          timestamp,
           status: "FILED".to_string(),
-          last_status_update: timestamp.clone(),
+          last_status_update: timestamp,
           proofs: Vec::new(),
+          public_key,
+          signature,
+          key_scheme,
+          sla_deadline,
         };
-        self.complaints.insert(complaint_id, new_complaint);
+        self.complaints.insert(complaint_id.clone(), new_complaint);
+        self.rebuild_merkle();
+        self.append_audit_event("Complaint.Register", EventCategory::Create, &actor, &complaint_id, &timestamp.to_wire_string());
         true
     }
 
     #[mutate]
-    async fn register_proof(&mut self, complaint_id: String, proof_hash: String, proof_type: String, timestamp: String) -> bool {
+    async fn register_proof(&mut self, complaint_id: String, proof_hash: String, proof_type: String, cid: String, mime_type: String, timestamp: Timestamp, public_key: String, signature: String, key_scheme: String) -> bool {
+        let Some(key_scheme) = KeyScheme::parse(&key_scheme) else {
+          return false;
+        };
         let complaint = match self.complaints.get_mut(&complaint_id) {
           Some(c) => c,
           None => return false,
         };
-        if complaint.status == "REJECTED" || complaint.status == "RESOLVED" {
+        if ComplaintStatus::parse(&complaint.status).is_some_and(|s| s.is_terminal()) {
           // the complaint has already been rejected or resloved so no need add proofs to it
           return false;
         }
+        if timestamp < complaint.timestamp {
+          // a proof can't predate the complaint it's evidence for
+          return false;
+        }
+        if !cid_matches_hash(&cid, &proof_hash) {
+          // the proof must actually be retrievable from the content-addressed location it claims
+          return false;
+        }
+        let Some(registered_key) = self.user_keys.get(&complaint.user_id) else {
+          return false;
+        };
+        if registered_key.public_key != public_key || registered_key.key_scheme != key_scheme {
+          return false;
+        }
+        let message = proof_message(&complaint_id, &proof_hash, &proof_type, &cid, &mime_type, timestamp);
+        if !verify_signature(&key_scheme, &public_key, &signature, &message) {
+          return false;
+        }
+        // derived from the verified signer, same reasoning as complaint_register
+        let actor = complaint.user_id.clone();
         let proof = ProofInfo {
           proof_hash,
           proof_type,
-          timestamp
+          cid,
+          mime_type,
+          timestamp,
+          public_key,
+          signature,
+          key_scheme,
         };
         complaint.proofs.push(proof);
+        self.update_merkle_path(&complaint_id);
+        self.append_audit_event("Proof.Submit", EventCategory::Create, &actor, &complaint_id, &timestamp.to_wire_string());
         true
     }
 
     #[mutate]
-    async fn update_complaint_status(&mut self, complaint_id: String, status: String, timestamp: String) -> bool {
+    async fn update_complaint_status(&mut self, complaint_id: String, status: String, timestamp: Timestamp, actor: String) -> bool {
+        let Some(new_status) = ComplaintStatus::parse(&status) else {
+          return false;
+        };
         let complaint = match self.complaints.get_mut(&complaint_id) {
           Some(c) => c,
           None => return false,
         };
-        if complaint.status == "REJECTED" || complaint.status == "RESOLVED" {
-          // the complaint has already been rejected or resloved so no need to update or change status
+        let Some(current_status) = ComplaintStatus::parse(&complaint.status) else {
           return false;
         };
+        if !current_status.allowed_next().contains(&new_status) {
+          // either a terminal state or an illegal jump (e.g. FILED -> RESOLVED without investigation)
+          return false;
+        }
+        if timestamp < complaint.last_status_update {
+          // reject out-of-order updates so last_status_update stays monotonic
+          return false;
+        }
         complaint.status = status;
         complaint.last_status_update = timestamp;
+        if new_status == ComplaintStatus::Escalated {
+          // same reasoning as escalate_overdue: leaving an already-elapsed deadline set would let
+          // the complaint bounce back to an escalatable status (e.g. Escalated -> UnderInvestigation)
+          // and get immediately re-escalated by the next escalate_overdue poll
+          complaint.sla_deadline = None;
+        }
+        self.update_merkle_path(&complaint_id);
+        self.append_audit_event("Complaint.UpdateStatus", EventCategory::Modify, &actor, &complaint_id, &timestamp.to_wire_string());
         true
+    }
 
+    #[query]
+    async fn allowed_transitions(&self, complaint_id: String) -> Vec<String> {
+        self.complaints
+          .get(&complaint_id)
+          .and_then(|complaint| ComplaintStatus::parse(&complaint.status))
+          .map(|status| status.allowed_next().iter().map(|s| s.as_str().to_string()).collect())
+          .unwrap_or_default()
+    }
+
+    #[mutate]
+    async fn escalate_overdue(&mut self, now: Timestamp, actor: String) -> Vec<String> {
+        let overdue_ids: Vec<String> = self
+          .complaints
+          .iter()
+          .filter(|(_, c)| is_escalatable(&c.status))
+          .filter(|(_, c)| c.sla_deadline.is_some_and(|deadline| now >= deadline))
+          .map(|(id, _)| id.clone())
+          .collect();
+        for complaint_id in &overdue_ids {
+          let Some(complaint) = self.complaints.get_mut(complaint_id) else {
+            continue;
+          };
+          complaint.status = ComplaintStatus::Escalated.as_str().to_string();
+          complaint.last_status_update = now;
+          // the breach is consumed by this escalation; leaving it set would make the next
+          // escalate_overdue poll immediately re-escalate as soon as the complaint moves back
+          // to UNDER_INVESTIGATION, since `now >= deadline` would still hold forever
+          complaint.sla_deadline = None;
+          self.update_merkle_path(complaint_id);
+          self.append_audit_event("Complaint.Escalate", EventCategory::Modify, &actor, complaint_id, &now.to_wire_string());
+        }
+        overdue_ids
+    }
+
+    #[query]
+    async fn get_overdue(&self, now: Timestamp) -> Vec<String> {
+        self.complaints
+          .iter()
+          .filter(|(_, c)| is_escalatable(&c.status))
+          .filter(|(_, c)| c.sla_deadline.is_some_and(|deadline| now >= deadline))
+          .map(|(id, _)| id.clone())
+          .collect()
     }
 
     #[query]
@@ -114,17 +658,113 @@ impl AuditLayer for AuditLayerContractState {
         self.complaints.get(&complaint_id).cloned().unwrap_or(ComplaintInfo {
           user_id: "".to_string(),
           complaint_hash: "".to_string(),
-          timestamp: "".to_string(),
+          timestamp: Timestamp { millis_since_epoch: 0, tz_offset_minutes: 0 },
           status: "".to_string(),
           proofs: Vec::new(),
-          last_status_update: "".to_string(),
+          last_status_update: Timestamp { millis_since_epoch: 0, tz_offset_minutes: 0 },
+          public_key: "".to_string(),
+          signature: "".to_string(),
+          key_scheme: KeyScheme::Ed25519,
+          sla_deadline: None,
         })
     }
 
+    #[query]
+    async fn get_complaint_proof(&self, complaint_id: String) -> ComplaintProof {
+        let Some(index) = self.complaints.keys().position(|id| id == &complaint_id) else {
+          return ComplaintProof { leaf_hash: "".to_string(), siblings: Vec::new() };
+        };
+        let leaf_hash = self.merkle_levels[0][index].clone();
+        let mut siblings = Vec::new();
+        let mut idx = index;
+        for level in 0..self.merkle_levels.len().saturating_sub(1) {
+          let level_len = self.merkle_levels[level].len();
+          let (sibling_idx, is_left) = if idx % 2 == 0 {
+            (if idx + 1 < level_len { idx + 1 } else { idx }, false)
+          } else {
+            (idx - 1, true)
+          };
+          siblings.push(MerkleSibling {
+            hash: self.merkle_levels[level][sibling_idx].clone(),
+            is_left,
+          });
+          idx /= 2;
+        }
+        ComplaintProof { leaf_hash, siblings }
+    }
+
+    #[query]
+    async fn get_proofs_by_type(&self, complaint_id: String, mime_type: String) -> Vec<ProofInfo> {
+        self.complaints
+          .get(&complaint_id)
+          .map(|complaint| complaint.proofs.iter().filter(|p| p.mime_type == mime_type).cloned().collect())
+          .unwrap_or_default()
+    }
+
+    #[query]
+    async fn complaints_root(&self) -> String {
+        self.merkle_levels.last().and_then(|level| level.first().cloned()).unwrap_or_default()
+    }
+
+    #[query]
+    async fn get_audit_log(&self) -> Vec<AuditEvent> {
+        self.audit_log.clone()
+    }
+
+    #[query]
+    async fn verify_audit_chain(&self) -> bool {
+        let mut prev_hash = genesis_hash();
+        for (seq, event) in self.audit_log.iter().enumerate() {
+          if event.seq != seq as u64 || event.prev_hash != prev_hash {
+            return false;
+          }
+          let expected = event_hash(&event.prev_hash, event.seq, &event.action_id, &event.actor, &event.target_complaint_id, &event.timestamp);
+          if expected != event.event_hash {
+            return false;
+          }
+          prev_hash = event.event_hash.clone();
+        }
+        true
+    }
+
 
     #[query]
     fn tools(&self) -> String {
         r#"[
+  {
+    "type": "function",
+    "function": {
+      "name": "register_user_key",
+      "description": "bind a user id to the public key that will be used to authenticate their future submissions; one-time binding, fails if user_id is already registered\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "user_id": {
+            "type": "string",
+            "description": "unique user id\n"
+          },
+          "public_key": {
+            "type": "string",
+            "description": "hex-encoded public key\n"
+          },
+          "key_scheme": {
+            "type": "string",
+            "description": "signature scheme of the key: ed25519 or secp256k1\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "identity of the caller performing this action, recorded in the audit log\n"
+          }
+        },
+        "required": [
+          "user_id",
+          "public_key",
+          "key_scheme",
+          "actor"
+        ]
+      }
+    }
+  },
   {
     "type": "function",
     "function": {
@@ -147,14 +787,33 @@ impl AuditLayer for AuditLayerContractState {
           },
           "timestamp": {
             "type": "string",
-            "description": "time at which the complaint is registered\n"
+            "description": "time at which the complaint is registered, formatted as `<millis_since_epoch>+<tz_offset_minutes>`\n"
+          },
+          "public_key": {
+            "type": "string",
+            "description": "hex-encoded public key of the submitting user, must match the key registered via register_user_key\n"
+          },
+          "signature": {
+            "type": "string",
+            "description": "hex-encoded signature over a \"complaint_register_v1\" domain tag followed by complaint_id || complaint_hash || user_id || timestamp, each field length-prefixed to prevent ambiguous concatenation\n"
+          },
+          "key_scheme": {
+            "type": "string",
+            "description": "signature scheme used: ed25519 or secp256k1\n"
+          },
+          "sla_deadline": {
+            "type": "string",
+            "description": "optional SLA deadline, formatted as `<millis_since_epoch>+<tz_offset_minutes>`; complaints still open past this time are surfaced by get_overdue and escalate_overdue\n"
           }
         },
         "required": [
           "complaint_id",
           "complaint_hash",
           "user_id",
-          "timestamp"
+          "timestamp",
+          "public_key",
+          "signature",
+          "key_scheme"
         ]
       }
     }
@@ -173,22 +832,47 @@ impl AuditLayer for AuditLayerContractState {
           },
           "proof_hash": {
             "type": "string",
-            "description": "SHA256 hash of the proof\n"
+            "description": "SHA256 hash of the proof; must match the multihash digest embedded in cid\n"
           },
           "proof_type": {
             "type": "string",
             "description": "type of the proof which has been submitted\n"
           },
+          "cid": {
+            "type": "string",
+            "description": "IPFS CIDv1 pointing at the content-addressed location where the proof can be retrieved\n"
+          },
+          "mime_type": {
+            "type": "string",
+            "description": "MIME type of the proof content, e.g. image/png or application/pdf\n"
+          },
           "timestamp": {
             "type": "string",
-            "description": "time at which the proof submitted\n"
+            "description": "time at which the proof submitted, formatted as `<millis_since_epoch>+<tz_offset_minutes>`; must not be earlier than the complaint's filing timestamp\n"
+          },
+          "public_key": {
+            "type": "string",
+            "description": "hex-encoded public key of the submitting user, must match the key registered for the complaint's user_id\n"
+          },
+          "signature": {
+            "type": "string",
+            "description": "hex-encoded signature over a \"register_proof_v1\" domain tag followed by complaint_id || proof_hash || proof_type || cid || mime_type || timestamp, each field length-prefixed to prevent ambiguous concatenation\n"
+          },
+          "key_scheme": {
+            "type": "string",
+            "description": "signature scheme used: ed25519 or secp256k1\n"
           }
         },
         "required": [
           "complaint_id",
           "proof_hash",
           "proof_type",
-          "timestamp"
+          "cid",
+          "mime_type",
+          "timestamp",
+          "public_key",
+          "signature",
+          "key_scheme"
         ]
       }
     }
@@ -197,27 +881,94 @@ impl AuditLayer for AuditLayerContractState {
     "type": "function",
     "function": {
       "name": "update_complaint_status",
-      "description": "used to update the complaint status\n",
+      "description": "update the complaint status; the requested status must be a legal next state for the complaint's current status, see allowed_transitions\n",
       "parameters": {
         "type": "object",
         "properties": {
           "complaint_id": {
             "type": "string",
-            "description": "complaint_id in which the status needs to be updated if the current status if REJECTED or RESOLVED we skip\n"
+            "description": "complaint_id in which the status needs to be updated\n"
           },
           "status": {
             "type": "string",
-            "description": "status of the complaint like FILED, UNDER_INVESTIGATION, RESOLVED, REJECTED\n"
+            "description": "one of FILED, UNDER_INVESTIGATION, ESCALATED, RESOLVED, REJECTED; rejected if it is not an allowed transition from the current status\n"
           },
           "timestamp": {
           "type": "string",
-          "description":"timestamp of the which the complaint status is updated\n"
+          "description":"timestamp of the which the complaint status is updated, formatted as `<millis_since_epoch>+<tz_offset_minutes>`; must not be earlier than the complaint's last_status_update\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "identity of the caller performing this action, recorded in the audit log\n"
           }
         },
         "required": [
           "complaint_id",
           "status",
-          "timestamp"
+          "timestamp",
+          "actor"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "allowed_transitions",
+      "description": "report the legal next statuses for a complaint given its current status, so callers can drive update_complaint_status correctly\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "complaint_id": {
+            "type": "string",
+            "description": "complaint id whose legal next states should be reported\n"
+          }
+        },
+        "required": [
+          "complaint_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "escalate_overdue",
+      "description": "transition every FILED or UNDER_INVESTIGATION complaint whose sla_deadline has elapsed to ESCALATED, returning the ids affected\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "now": {
+            "type": "string",
+            "description": "current time, formatted as `<millis_since_epoch>+<tz_offset_minutes>`\n"
+          },
+          "actor": {
+            "type": "string",
+            "description": "identity of the caller performing this action, recorded in the audit log\n"
+          }
+        },
+        "required": [
+          "now",
+          "actor"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_overdue",
+      "description": "report FILED or UNDER_INVESTIGATION complaints whose sla_deadline has elapsed, without changing their status\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "now": {
+            "type": "string",
+            "description": "current time, formatted as `<millis_since_epoch>+<tz_offset_minutes>`\n"
+          }
+        },
+        "required": [
+          "now"
         ]
       }
     }
@@ -252,6 +1003,85 @@ impl AuditLayer for AuditLayerContractState {
         ]
       }
     }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_complaint_proof",
+      "description": "get a merkle inclusion proof for a complaint, proving it is part of the committed audit state without downloading the whole complaint set\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "complaint_id": {
+            "type": "string",
+            "description": "complaint id to prove inclusion of\n"
+          }
+        },
+        "required": [
+          "complaint_id"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_proofs_by_type",
+      "description": "fetch only the proofs attached to a complaint whose mime_type matches, e.g. just the image/pdf evidence\n",
+      "parameters": {
+        "type": "object",
+        "properties": {
+          "complaint_id": {
+            "type": "string",
+            "description": "complaint id whose proofs should be filtered\n"
+          },
+          "mime_type": {
+            "type": "string",
+            "description": "MIME type to filter proofs by, e.g. image/png or application/pdf\n"
+          }
+        },
+        "required": [
+          "complaint_id",
+          "mime_type"
+        ]
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "complaints_root",
+      "description": "get the current merkle root over all complaints, used to verify inclusion proofs returned by get_complaint_proof\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "get_audit_log",
+      "description": "get the full hash-chained provenance trail of every mutation applied to this contract\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
+  },
+  {
+    "type": "function",
+    "function": {
+      "name": "verify_audit_chain",
+      "description": "verify that the audit log's hash chain has not been tampered with\n",
+      "parameters": {
+        "type": "object",
+        "properties": {},
+        "required": []
+      }
+    }
   }
 ]"#.to_string()
     }
@@ -265,3 +1095,284 @@ impl AuditLayer for AuditLayerContractState {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_complaint(i: u32) -> ComplaintInfo {
+        ComplaintInfo {
+          user_id: format!("user{i}"),
+          complaint_hash: format!("hash{i}"),
+          timestamp: Timestamp { millis_since_epoch: i as i64, tz_offset_minutes: 0 },
+          status: "FILED".to_string(),
+          last_status_update: Timestamp { millis_since_epoch: i as i64, tz_offset_minutes: 0 },
+          proofs: Vec::new(),
+          public_key: "pk".to_string(),
+          signature: "sig".to_string(),
+          key_scheme: KeyScheme::Ed25519,
+          sla_deadline: None,
+        }
+    }
+
+    fn empty_state() -> AuditLayerContractState {
+        AuditLayerContractState {
+          complaints: BTreeMap::new(),
+          user_keys: BTreeMap::new(),
+          merkle_levels: Vec::new(),
+          audit_log: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn update_merkle_path_matches_full_rebuild() {
+        let mut state = empty_state();
+        for i in 0..5 {
+          state.complaints.insert(format!("c{i}"), sample_complaint(i));
+        }
+        state.rebuild_merkle();
+
+        state.complaints.get_mut("c2").unwrap().status = "UNDER_INVESTIGATION".to_string();
+        state.update_merkle_path("c2");
+        let incremental = state.merkle_levels.clone();
+
+        state.rebuild_merkle();
+        assert_eq!(incremental, state.merkle_levels, "incremental update must agree with a full rebuild");
+    }
+
+    #[test]
+    fn verify_audit_chain_detects_tampering() {
+        let mut state = empty_state();
+        state.append_audit_event("Complaint.Register", EventCategory::Create, "alice", "c1", "1000+0");
+        state.append_audit_event("Complaint.UpdateStatus", EventCategory::Modify, "bob", "c1", "2000+0");
+
+        assert!(futures::executor::block_on(state.verify_audit_chain()));
+
+        // mutate a past entry in place without recomputing its hash, as real tampering would
+        state.audit_log[0].actor = "mallory".to_string();
+        assert!(!futures::executor::block_on(state.verify_audit_chain()));
+    }
+
+    #[test]
+    fn complaint_register_verifies_ed25519_round_trip() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let mut state = empty_state();
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        assert!(futures::executor::block_on(state.register_user_key(
+          "user1".to_string(), public_key.clone(), "ed25519".to_string(), "admin".to_string(),
+        )));
+
+        let timestamp = Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 0 };
+        let message = complaint_message("c1", "hash1", "user1", timestamp);
+        let signature = hex::encode(signing_key.sign(&message).to_bytes());
+
+        assert!(futures::executor::block_on(state.complaint_register(
+          "c1".to_string(), "hash1".to_string(), "user1".to_string(), timestamp,
+          public_key, signature, "ed25519".to_string(), None,
+        )));
+    }
+
+    #[test]
+    fn complaint_register_rejects_secp256k1_signature_over_a_different_field() {
+        use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let mut state = empty_state();
+        let signing_key = SigningKey::from_slice(&[9u8; 32]).unwrap();
+        let public_key = hex::encode(signing_key.verifying_key().to_sec1_bytes());
+        assert!(futures::executor::block_on(state.register_user_key(
+          "user1".to_string(), public_key.clone(), "secp256k1".to_string(), "admin".to_string(),
+        )));
+
+        let timestamp = Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 0 };
+        // sign a different complaint_hash than the one actually submitted
+        let message = complaint_message("c1", "wrong-hash", "user1", timestamp);
+        let signature: Signature = signing_key.sign(&message);
+
+        assert!(!futures::executor::block_on(state.complaint_register(
+          "c1".to_string(), "hash1".to_string(), "user1".to_string(), timestamp,
+          public_key, hex::encode(signature.to_bytes()), "secp256k1".to_string(), None,
+        )));
+    }
+
+    #[test]
+    fn register_user_key_rejects_an_already_registered_user_id() {
+        let mut state = empty_state();
+        assert!(futures::executor::block_on(state.register_user_key(
+          "user1".to_string(), "pk1".to_string(), "ed25519".to_string(), "admin".to_string(),
+        )));
+        // a second registration for the same user_id must not overwrite the first binding
+        assert!(!futures::executor::block_on(state.register_user_key(
+          "user1".to_string(), "pk2".to_string(), "secp256k1".to_string(), "admin".to_string(),
+        )));
+        assert_eq!(state.user_keys["user1"].public_key, "pk1");
+        assert_eq!(state.user_keys["user1"].key_scheme, KeyScheme::Ed25519);
+    }
+
+    #[test]
+    fn timestamp_orders_by_millis_ignoring_tz_offset() {
+        let earlier = Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 300 };
+        let later = Timestamp { millis_since_epoch: 2000, tz_offset_minutes: -300 };
+        assert!(earlier < later);
+        assert_eq!(earlier, Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 0 });
+    }
+
+    #[test]
+    fn timestamp_wire_string_round_trips() {
+        let ts = Timestamp { millis_since_epoch: 1234567, tz_offset_minutes: -330 };
+        let parsed = Timestamp::parse_wire_string(&ts.to_wire_string()).unwrap();
+        assert_eq!(parsed, ts);
+    }
+
+    #[test]
+    fn update_complaint_status_rejects_out_of_order_timestamp() {
+        let mut state = empty_state();
+        let mut complaint = sample_complaint(1);
+        complaint.last_status_update = Timestamp { millis_since_epoch: 5000, tz_offset_minutes: 0 };
+        state.complaints.insert("c1".to_string(), complaint);
+        state.rebuild_merkle();
+
+        let earlier = Timestamp { millis_since_epoch: 4000, tz_offset_minutes: 0 };
+        assert!(!futures::executor::block_on(state.update_complaint_status(
+          "c1".to_string(), "UNDER_INVESTIGATION".to_string(), earlier, "admin".to_string(),
+        )));
+    }
+
+    #[test]
+    fn escalate_overdue_transitions_and_clears_the_deadline_once() {
+        let mut state = empty_state();
+        let mut complaint = sample_complaint(1);
+        complaint.sla_deadline = Some(Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 0 });
+        state.complaints.insert("c1".to_string(), complaint);
+        state.rebuild_merkle();
+
+        let now = Timestamp { millis_since_epoch: 2000, tz_offset_minutes: 0 };
+        let escalated = futures::executor::block_on(state.escalate_overdue(now, "admin".to_string()));
+        assert_eq!(escalated, vec!["c1".to_string()]);
+        assert_eq!(state.complaints["c1"].status, "ESCALATED");
+        assert_eq!(state.complaints["c1"].sla_deadline, None);
+
+        // a second poll at the same `now` finds nothing: the complaint is already ESCALATED
+        let escalated_again = futures::executor::block_on(state.escalate_overdue(now, "admin".to_string()));
+        assert!(escalated_again.is_empty());
+    }
+
+    #[test]
+    fn update_complaint_status_clears_sla_deadline_on_manual_escalation() {
+        let mut state = empty_state();
+        let mut complaint = sample_complaint(1);
+        complaint.sla_deadline = Some(Timestamp { millis_since_epoch: 1000, tz_offset_minutes: 0 });
+        state.complaints.insert("c1".to_string(), complaint);
+        state.rebuild_merkle();
+
+        let now = Timestamp { millis_since_epoch: 2000, tz_offset_minutes: 0 };
+        assert!(futures::executor::block_on(state.update_complaint_status(
+          "c1".to_string(), "ESCALATED".to_string(), now, "admin".to_string(),
+        )));
+        assert_eq!(state.complaints["c1"].sla_deadline, None);
+
+        // bouncing back to an escalatable status must not resurrect the already-elapsed deadline
+        assert!(futures::executor::block_on(state.update_complaint_status(
+          "c1".to_string(), "UNDER_INVESTIGATION".to_string(), now, "admin".to_string(),
+        )));
+        assert!(futures::executor::block_on(state.escalate_overdue(now, "admin".to_string())).is_empty());
+    }
+
+    #[test]
+    fn get_overdue_ignores_complaints_without_a_deadline() {
+        let mut state = empty_state();
+        state.complaints.insert("c1".to_string(), sample_complaint(1));
+        let now = Timestamp { millis_since_epoch: 999_999, tz_offset_minutes: 0 };
+        assert!(futures::executor::block_on(state.get_overdue(now)).is_empty());
+    }
+
+    #[test]
+    fn cid_matches_hash_accepts_a_genuine_sha2_256_cid() {
+        let digest_hex = sha256_hex(b"proof-bytes");
+        let digest_bytes = hex::decode(&digest_hex).unwrap();
+        let mh = multihash::Multihash::<64>::wrap(0x12, &digest_bytes).unwrap();
+        let cid = cid::Cid::new_v1(0x55, mh).to_string();
+        assert!(cid_matches_hash(&cid, &digest_hex));
+    }
+
+    #[test]
+    fn cid_matches_hash_rejects_an_identity_multihash() {
+        // an identity multihash (code 0x00) just embeds whatever bytes the attacker chose as its
+        // "digest", so it can always be made to equal any proof_hash without hashing anything
+        let claimed_hash = sha256_hex(b"attacker-controlled-bytes");
+        let claimed_bytes = hex::decode(&claimed_hash).unwrap();
+        let mh = multihash::Multihash::<64>::wrap(0x00, &claimed_bytes).unwrap();
+        let cid = cid::Cid::new_v1(0x55, mh).to_string();
+        assert!(!cid_matches_hash(&cid, &claimed_hash));
+    }
+
+    #[test]
+    fn register_proof_accepts_a_validly_signed_content_addressed_proof() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let mut state = empty_state();
+        let signing_key = SigningKey::from_bytes(&[3u8; 32]);
+        let public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        assert!(futures::executor::block_on(state.register_user_key(
+          "user1".to_string(), public_key.clone(), "ed25519".to_string(), "admin".to_string(),
+        )));
+
+        let mut complaint = sample_complaint(1);
+        complaint.user_id = "user1".to_string();
+        state.complaints.insert("c1".to_string(), complaint);
+        state.rebuild_merkle();
+
+        let proof_hash = sha256_hex(b"proof-bytes");
+        let digest_bytes = hex::decode(&proof_hash).unwrap();
+        let mh = multihash::Multihash::<64>::wrap(0x12, &digest_bytes).unwrap();
+        let cid = cid::Cid::new_v1(0x55, mh).to_string();
+
+        let timestamp = Timestamp { millis_since_epoch: 5, tz_offset_minutes: 0 };
+        let message = proof_message("c1", &proof_hash, "photo", &cid, "image/png", timestamp);
+        let signature = hex::encode(signing_key.sign(&message).to_bytes());
+
+        assert!(futures::executor::block_on(state.register_proof(
+          "c1".to_string(), proof_hash, "photo".to_string(), cid, "image/png".to_string(), timestamp,
+          public_key, signature, "ed25519".to_string(),
+        )));
+
+        let proofs = futures::executor::block_on(state.get_proofs_by_type("c1".to_string(), "image/png".to_string()));
+        assert_eq!(proofs.len(), 1);
+        assert_eq!(futures::executor::block_on(state.get_complaint("c1".to_string())).proofs.len(), 1);
+    }
+
+    #[test]
+    fn update_complaint_status_rejects_an_illegal_jump() {
+        let mut state = empty_state();
+        state.complaints.insert("c1".to_string(), sample_complaint(1));
+        state.rebuild_merkle();
+
+        let later = Timestamp { millis_since_epoch: 2, tz_offset_minutes: 0 };
+        // FILED -> RESOLVED skips investigation/escalation entirely
+        assert!(!futures::executor::block_on(state.update_complaint_status(
+          "c1".to_string(), "RESOLVED".to_string(), later, "admin".to_string(),
+        )));
+    }
+
+    #[test]
+    fn update_complaint_status_rejects_transition_from_a_terminal_state() {
+        let mut state = empty_state();
+        let mut complaint = sample_complaint(1);
+        complaint.status = "RESOLVED".to_string();
+        state.complaints.insert("c1".to_string(), complaint);
+        state.rebuild_merkle();
+
+        let later = Timestamp { millis_since_epoch: 2, tz_offset_minutes: 0 };
+        assert!(!futures::executor::block_on(state.update_complaint_status(
+          "c1".to_string(), "UNDER_INVESTIGATION".to_string(), later, "admin".to_string(),
+        )));
+    }
+
+    #[test]
+    fn allowed_transitions_reports_the_legal_next_states() {
+        let mut state = empty_state();
+        state.complaints.insert("c1".to_string(), sample_complaint(1));
+        let next = futures::executor::block_on(state.allowed_transitions("c1".to_string()));
+        assert_eq!(next, vec!["UNDER_INVESTIGATION", "ESCALATED", "REJECTED"]);
+    }
+}